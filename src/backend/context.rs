@@ -0,0 +1,241 @@
+use winit::event_loop::ControlFlow;
+use winit::event_loop::EventLoopWindowTarget;
+
+use crate::ContextHandle;
+use crate::WindowHandle;
+use crate::WindowId;
+use crate::backend::proxy::ContextEvent;
+use crate::backend::proxy::ContextThreadGuard;
+use crate::event::Event;
+use crate::event::EventHandlerControlFlow;
+use crate::event::WindowEvent;
+
+/// Boxed event handler for global context events.
+type ContextEventHandler<UserEvent> =
+	Box<dyn FnMut(ContextHandle<UserEvent>, &mut Event<UserEvent>, &mut EventHandlerControlFlow) + Send>;
+
+/// Boxed event handler for window events.
+type WindowEventHandler<UserEvent> =
+	Box<dyn FnMut(WindowHandle<UserEvent>, &mut WindowEvent, &mut EventHandlerControlFlow) + Send>;
+
+/// The global context running on the context thread.
+///
+/// The context owns the windows and the registered event handlers, and is driven by
+/// the winit event loop. All mutation happens on the context thread; other threads
+/// interact with it through a [`ContextProxy`][crate::ContextProxy].
+pub struct Context<UserEvent: 'static> {
+	/// The windows managed by the context.
+	pub(crate) windows: Vec<Window<UserEvent>>,
+
+	/// The global event handlers.
+	pub(crate) event_handlers: Vec<ContextEventHandler<UserEvent>>,
+}
+
+/// A window managed by the context, together with its per-window event handlers.
+pub(crate) struct Window<UserEvent: 'static> {
+	/// The window ID.
+	pub(crate) id: WindowId,
+
+	/// The event handlers registered for this specific window.
+	pub(crate) event_handlers: Vec<WindowEventHandler<UserEvent>>,
+}
+
+/// Dispatch an event to a list of handlers, honouring the [`EventHandlerControlFlow`] each sets.
+///
+/// `invoke` is called with each handler in turn and returns the control object that handler
+/// set; after it returns, the handler is removed from `handlers` if it requested `remove_handler`, and iteration stops
+/// early if it requested `stop_propagation`. Removal during iteration is handled so the
+/// index never runs past a handler that was dropped.
+///
+/// Returns `true` if a handler stopped propagation.
+fn dispatch_handlers<H, F>(handlers: &mut Vec<H>, mut invoke: F) -> bool
+where
+	F: FnMut(&mut H) -> EventHandlerControlFlow,
+{
+	let mut i = 0;
+	while i < handlers.len() {
+		let control = invoke(&mut handlers[i]);
+
+		if control.remove_handler {
+			handlers.remove(i);
+		} else {
+			i += 1;
+		}
+
+		if control.stop_propagation {
+			return true;
+		}
+	}
+
+	false
+}
+
+impl<UserEvent> Context<UserEvent> {
+	/// Handle a single winit event on the context thread.
+	pub(crate) fn handle_event(
+		&mut self,
+		event: winit::event::Event<ContextEvent<UserEvent>>,
+		event_loop: &EventLoopWindowTarget<ContextEvent<UserEvent>>,
+		_control_flow: &mut ControlFlow,
+	) {
+		// Mark this thread as the context thread for the duration of the dispatch so that
+		// blocking proxy calls made from within a handler are detected instead of deadlocking.
+		let _guard = ContextThreadGuard::new();
+
+		match event {
+			// A function posted through the proxy: run it with a handle to the context.
+			winit::event::Event::UserEvent(ContextEvent::ExecuteFunction(function)) => {
+				let mut context_handle = ContextHandle::new(self, event_loop);
+				(function.function)(&mut context_handle);
+			},
+
+			// A bare wakeup posted through a `ContextWaker`: run the global event handlers
+			// with an `Event::Wake` so they can drain whatever external queue they own.
+			winit::event::Event::UserEvent(ContextEvent::Wake) => {
+				let mut event = Event::Wake;
+				self.run_event_handlers(&mut event, event_loop);
+			},
+
+			// A user event: deliver it to the global event handlers.
+			winit::event::Event::UserEvent(ContextEvent::UserEvent(user_event)) => {
+				let mut event = Event::UserEvent(user_event);
+				self.run_event_handlers(&mut event, event_loop);
+			},
+
+			// A window event from winit: deliver it to the window and global handlers.
+			winit::event::Event::WindowEvent { window_id, event } => {
+				if let Some(window_event) = WindowEvent::from_winit_event(window_id, event) {
+					self.run_window_event_handlers(window_event, event_loop);
+				}
+			},
+
+			_ => (),
+		}
+	}
+
+	/// Run the registered global event handlers for a single event.
+	///
+	/// After invoking each handler, the handler's [`EventHandlerControlFlow`] is inspected:
+	/// if `remove_handler` is set the handler is dropped from the registry, and if
+	/// `stop_propagation` is set the remaining handlers are skipped for this event.
+	///
+	/// Handlers are temporarily moved out of the context so that a handler is free to
+	/// register new handlers (or otherwise borrow the context) while it runs; any newly
+	/// registered handlers are appended after the surviving ones.
+	pub(crate) fn run_event_handlers(
+		&mut self,
+		event: &mut Event<UserEvent>,
+		event_loop: &EventLoopWindowTarget<ContextEvent<UserEvent>>,
+	) {
+		let mut handlers = std::mem::take(&mut self.event_handlers);
+
+		dispatch_handlers(&mut handlers, |handler| {
+			let mut control = EventHandlerControlFlow::default();
+			let context_handle = ContextHandle::new(self, event_loop);
+			handler(context_handle, event, &mut control);
+			control
+		});
+
+		// Keep the surviving handlers first, then any handlers registered during dispatch.
+		handlers.append(&mut self.event_handlers);
+		self.event_handlers = handlers;
+	}
+
+	/// Run the event handlers for the window the event was generated for, then the global handlers.
+	///
+	/// The per-window handlers are inspected for the same [`EventHandlerControlFlow`] flags as
+	/// [`Self::run_event_handlers`]; if a window handler stops propagation, the event is not
+	/// forwarded to the remaining window handlers nor to the global handlers.
+	pub(crate) fn run_window_event_handlers(
+		&mut self,
+		mut event: WindowEvent,
+		event_loop: &EventLoopWindowTarget<ContextEvent<UserEvent>>,
+	) {
+		let window_index = match self.windows.iter().position(|window| window.id == event.window_id()) {
+			Some(index) => index,
+			None => return,
+		};
+
+		let window_id = self.windows[window_index].id;
+		let mut handlers = std::mem::take(&mut self.windows[window_index].event_handlers);
+
+		let stop_propagation = dispatch_handlers(&mut handlers, |handler| {
+			let mut control = EventHandlerControlFlow::default();
+			let window_handle = WindowHandle::new(self, window_id, event_loop);
+			handler(window_handle, &mut event, &mut control);
+			control
+		});
+
+		// A handler may have mutated `self.windows` (e.g. by destroying its own window),
+		// so the saved index is no longer trustworthy. Re-locate the window by id, and
+		// simply drop the handlers if the window is gone.
+		if let Some(window) = self.windows.iter_mut().find(|window| window.id == window_id) {
+			handlers.append(&mut window.event_handlers);
+			window.event_handlers = handlers;
+		}
+
+		if !stop_propagation {
+			let mut event = Event::WindowEvent(event);
+			self.run_event_handlers(&mut event, event_loop);
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn dispatch_honours_remove_and_stop() {
+		let mut handlers = vec![1u32, 2, 3, 4];
+		let mut seen = Vec::new();
+
+		let stopped = dispatch_handlers(&mut handlers, |handler| {
+			seen.push(*handler);
+			let mut control = EventHandlerControlFlow::default();
+			if *handler == 2 {
+				control.remove_handler(); // self-removal in the middle of iteration
+			}
+			if *handler == 3 {
+				control.stop_propagation();
+			}
+			control
+		});
+
+		assert!(stopped);
+		assert_eq!(seen, vec![1, 2, 3]); // handler 4 is never reached after propagation stops
+		assert_eq!(handlers, vec![1, 3, 4]); // handler 2 removed; the index stayed correct
+	}
+
+	#[test]
+	fn dispatch_runs_every_handler_when_nothing_stops() {
+		let mut handlers = vec![1u32, 2, 3];
+		let mut count = 0;
+
+		let stopped = dispatch_handlers(&mut handlers, |_handler| {
+			count += 1;
+			EventHandlerControlFlow::default()
+		});
+
+		assert!(!stopped);
+		assert_eq!(count, 3);
+		assert_eq!(handlers, vec![1, 2, 3]);
+	}
+
+	#[test]
+	fn handler_destroying_a_window_during_dispatch_is_safe() {
+		// Model the context's window list; a handler destroys an *earlier* window mid-dispatch,
+		// which shifts every later index. Re-locating by id (as the dispatch now does) keeps working.
+		let mut windows = vec![10u32, 20, 30];
+		let mut handlers = vec![(), ()]; // two handlers attached to window 20
+
+		dispatch_handlers(&mut handlers, |_handler| {
+			windows.retain(|id| *id != 10);
+			EventHandlerControlFlow::default()
+		});
+
+		assert_eq!(windows, vec![20, 30]);
+		// The window the handlers belong to (20) is still present and findable by id.
+		assert!(windows.iter().any(|id| *id == 20));
+	}
+}
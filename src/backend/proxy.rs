@@ -1,7 +1,8 @@
 use crate::ContextHandle;
 use crate::WindowHandle;
-use crate::EventHandlerOutput;
+use crate::event::EventHandlerControlFlow;
 use crate::Image;
+use crate::ImageInfo;
 use crate::WindowId;
 use crate::WindowOptions;
 use crate::error::EventLoopClosedError;
@@ -38,6 +39,11 @@ impl<UserEvent: 'static> Clone for ContextProxy<UserEvent> {
 pub enum ContextEvent<UserEvent: 'static> {
 	ExecuteFunction(ExecuteFunction<UserEvent>),
 	UserEvent(UserEvent),
+
+	/// A payload-less event used only to wake the event loop.
+	///
+	/// See [`ContextWaker`] for details.
+	Wake,
 }
 
 pub struct ExecuteFunction<UserEvent: 'static> {
@@ -50,6 +56,83 @@ impl<UserEvent> From<ExecuteFunction<UserEvent>> for ContextEvent<UserEvent> {
 	}
 }
 
+/// A cheap, cloneable handle that can only wake the context event loop.
+///
+/// Unlike [`ContextProxy::run_function`], waking the event loop carries no payload,
+/// so it never boxes a closure or allocates.
+/// This makes it suitable for high-frequency producers that park their data in an
+/// external queue and only need to nudge the context thread into draining it.
+///
+/// The typical pattern is to register a context event handler that drains a shared
+/// `mpsc` (or lock-free) queue, push items onto that queue from any thread, and call
+/// [`ContextWaker::wake`] once per item (or once per batch) to schedule a drain.
+///
+/// A `ContextWaker` is `Clone`, `Send` and `Sync`, so it can be shared between producer threads.
+pub struct ContextWaker<UserEvent: 'static> {
+	event_loop: EventLoopProxy<UserEvent>,
+}
+
+impl<UserEvent> Clone for ContextWaker<UserEvent> {
+	fn clone(&self) -> Self {
+		Self { event_loop: self.event_loop.clone() }
+	}
+}
+
+impl<UserEvent> ContextWaker<UserEvent> {
+	/// Wrap an [`EventLoopProxy`] in a [`ContextWaker`].
+	pub(crate) fn new(event_loop: EventLoopProxy<UserEvent>) -> Self {
+		Self { event_loop }
+	}
+
+	/// Wake the context event loop without posting any work.
+	///
+	/// This sends a payload-less [`ContextEvent::Wake`] to the event loop.
+	/// It is up to a registered context event handler to react to the wakeup,
+	/// usually by draining an external queue filled by the caller.
+	pub fn wake(&self) -> Result<(), EventLoopClosedError> {
+		self.event_loop.send_event(ContextEvent::Wake).map_err(|_| EventLoopClosedError)
+	}
+}
+
+/// Statically assert that [`ContextWaker`] upholds its documented `Send + Sync` contract.
+///
+/// `winit::event_loop::EventLoopProxy` is `Send` but its `Sync`-ness has varied between
+/// winit versions, so this guards against silently shipping a `!Sync` waker.
+fn _assert_context_waker_send_sync()
+where
+	ContextWaker<()>: Clone + Send + Sync,
+{
+}
+
+thread_local! {
+	/// Set while the context thread is dispatching a posted function or event handler.
+	///
+	/// Used by [`ContextProxy::run_function_wait`] to detect re-entrant blocking calls
+	/// that would otherwise deadlock the event loop.
+	static ON_CONTEXT_THREAD: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+/// RAII guard that marks the current thread as the context thread for its lifetime.
+///
+/// The context event loop wraps each dispatch in one of these so that
+/// [`ContextProxy::run_function_wait`] can detect and reject re-entrant blocking calls.
+pub(crate) struct ContextThreadGuard {
+	_priv: (),
+}
+
+impl ContextThreadGuard {
+	pub(crate) fn new() -> Self {
+		ON_CONTEXT_THREAD.with(|flag| flag.set(true));
+		Self { _priv: () }
+	}
+}
+
+impl Drop for ContextThreadGuard {
+	fn drop(&mut self) {
+		ON_CONTEXT_THREAD.with(|flag| flag.set(false));
+	}
+}
+
 impl<UserEvent> ContextProxy<UserEvent> {
 	/// Wrap an [`EventLoopProxy`] in a [`ContextProxy`].
 	pub(crate) fn new(event_loop: EventLoopProxy<UserEvent>) -> Self {
@@ -60,6 +143,9 @@ impl<UserEvent> ContextProxy<UserEvent> {
 	///
 	/// The real work is done in the context thread.
 	/// This function blocks until the context thread has performed the action.
+	///
+	/// # Panics
+	/// Panics if called from within a context event handler; see [`Self::run_function_wait`].
 	pub fn create_window(
 		&self,
 		title: impl Into<String>,
@@ -78,6 +164,9 @@ impl<UserEvent> ContextProxy<UserEvent> {
 	///
 	/// The real work is done in the context thread.
 	/// This function blocks until the context thread has performed the action.
+	///
+	/// # Panics
+	/// Panics if called from within a context event handler; see [`Self::run_function_wait`].
 	pub fn destroy_window(
 		&self,
 		window_id: WindowId,
@@ -92,6 +181,9 @@ impl<UserEvent> ContextProxy<UserEvent> {
 	///
 	/// The real work is done in the context thread.
 	/// This function blocks until the context thread has performed the action.
+	///
+	/// # Panics
+	/// Panics if called from within a context event handler; see [`Self::run_function_wait`].
 	pub fn set_window_visible(
 		&self,
 		window_id: WindowId,
@@ -107,6 +199,9 @@ impl<UserEvent> ContextProxy<UserEvent> {
 	///
 	/// The real work is done in the context thread.
 	/// This function blocks until the context thread has performed the action.
+	///
+	/// # Panics
+	/// Panics if called from within a context event handler; see [`Self::run_function_wait`].
 	pub fn set_window_image(
 		&self,
 		window_id: WindowId,
@@ -121,6 +216,71 @@ impl<UserEvent> ContextProxy<UserEvent> {
 		Ok(())
 	}
 
+	/// Set an overlay for a window.
+	///
+	/// Overlays are named layers drawn on top of the base image set with
+	/// [`Self::set_window_image`]. Setting an overlay with a name that already
+	/// exists replaces it.
+	///
+	/// The real work is done in the context thread.
+	/// This function blocks until the context thread has performed the action.
+	///
+	/// # Panics
+	/// Panics if called from within a context event handler; see [`Self::run_function_wait`].
+	pub fn set_window_overlay(
+		&self,
+		window_id: WindowId,
+		name: impl Into<String>,
+		image: impl Into<Image<'static>>,
+		visible: bool,
+	) -> Result<(), ProxyWindowOperationError> {
+		let name = name.into();
+		let image = image.into();
+		self.run_function_wait(move |context| {
+			context.set_window_overlay(window_id, &name, &image, visible)
+		})??;
+		Ok(())
+	}
+
+	/// Remove an overlay from a window.
+	///
+	/// The real work is done in the context thread.
+	/// This function blocks until the context thread has performed the action.
+	///
+	/// # Panics
+	/// Panics if called from within a context event handler; see [`Self::run_function_wait`].
+	pub fn clear_window_overlay(
+		&self,
+		window_id: WindowId,
+		name: impl Into<String>,
+	) -> Result<(), ProxyWindowOperationError> {
+		let name = name.into();
+		self.run_function_wait(move |context| {
+			context.clear_window_overlay(window_id, &name)
+		})??;
+		Ok(())
+	}
+
+	/// Make an overlay of a window visible or invisible.
+	///
+	/// The real work is done in the context thread.
+	/// This function blocks until the context thread has performed the action.
+	///
+	/// # Panics
+	/// Panics if called from within a context event handler; see [`Self::run_function_wait`].
+	pub fn set_window_overlay_visible(
+		&self,
+		window_id: WindowId,
+		name: impl Into<String>,
+		visible: bool,
+	) -> Result<(), ProxyWindowOperationError> {
+		let name = name.into();
+		self.run_function_wait(move |context| {
+			context.set_window_overlay_visible(window_id, &name, visible)
+		})??;
+		Ok(())
+	}
+
 	/// Add a global event handler to the context.
 	///
 	/// Events that are already queued with the event loop will not be passed to the handler.
@@ -129,7 +289,7 @@ impl<UserEvent> ContextProxy<UserEvent> {
 	/// To avoid blocking, you can use [`Self::run_function`] to post a lambda that adds an error handler instead.
 	pub fn add_event_handler<F>(&mut self, handler: F) -> Result<(), EventLoopClosedError>
 	where
-		F: FnMut(ContextHandle<UserEvent>, &mut Event<UserEvent>) -> EventHandlerOutput + Send + 'static,
+		F: FnMut(ContextHandle<UserEvent>, &mut Event<UserEvent>, &mut EventHandlerControlFlow) + Send + 'static,
 	{
 		self.run_function_wait(move |context| {
 			context.add_event_handler(handler)
@@ -144,7 +304,7 @@ impl<UserEvent> ContextProxy<UserEvent> {
 	/// To avoid blocking, you can use [`Self::run_function`] to post a lambda that adds an error handler instead.
 	pub fn add_window_event_handler<F>(&mut self, window_id: WindowId, handler: F) -> Result<(), ProxyWindowOperationError>
 	where
-		F: FnMut(WindowHandle<UserEvent>, &mut WindowEvent) -> EventHandlerOutput + Send + 'static,
+		F: FnMut(WindowHandle<UserEvent>, &mut WindowEvent, &mut EventHandlerControlFlow) + Send + 'static,
 	{
 		self.run_function_wait(move |context| {
 			context.add_window_event_handler(window_id, handler)
@@ -177,11 +337,25 @@ impl<UserEvent> ContextProxy<UserEvent> {
 	/// *Note:*
 	/// You should not post functions to the context thread that block for a long time.
 	/// Doing so will block the event loop and will make the windows unresponsive until the event loop can continue.
+	///
+	/// # Panics
+	/// Panics if called from within a context event handler (that is, from the context thread itself),
+	/// since waiting for the posted function to run would deadlock the event loop.
+	/// Use the [`ContextHandle`]/[`WindowHandle`] passed to the handler, or [`Self::run_function`], instead.
 	pub fn run_function_wait<F, T>(&self, function: F) -> Result<T, EventLoopClosedError>
 	where
 		F: FnOnce(&mut ContextHandle<UserEvent>) -> T + Send + 'static,
 		T: Send + 'static,
 	{
+		// Blocking on the result channel from the context thread itself would deadlock:
+		// the posted function can only run once the current dispatch returns, which never happens.
+		assert!(
+			!ON_CONTEXT_THREAD.with(std::cell::Cell::get),
+			"run_function_wait was called from the context thread, which would deadlock the event loop. \
+			 From inside an event handler, use the ContextHandle/WindowHandle passed to you, \
+			 or run_function for non-blocking work.",
+		);
+
 		let (result_tx, result_rx) = oneshot::channel();
 		self.run_function(move |context| {
 			result_tx.send((function)(context))
@@ -189,6 +363,151 @@ impl<UserEvent> ContextProxy<UserEvent> {
 		result_rx.recv().map_err(|_| EventLoopClosedError)
 	}
 
+	/// Get a [`ContextWaker`] for this proxy.
+	///
+	/// The returned handle can only wake the event loop; it carries no payload and
+	/// never allocates, which makes it cheaper than [`Self::run_function`] for
+	/// high-frequency producers that deliver their data through a separate channel.
+	pub fn waker(&self) -> ContextWaker<UserEvent> {
+		ContextWaker::new(self.event_loop.clone())
+	}
+
+	/// Post a function for execution in the context thread and await the return value.
+	///
+	/// This is the non-blocking counterpart of [`Self::run_function_wait`]:
+	/// it posts the closure and returns a future that resolves once the context
+	/// thread has run it, without blocking the calling thread.
+	/// This makes it usable from an async task driven by Tokio, async-std, or any
+	/// other executor.
+	///
+	/// *Note:*
+	/// You should not post functions to the context thread that block for a long time.
+	/// Doing so will block the event loop and will make the windows unresponsive until the event loop can continue.
+	pub fn run_function_async<F, T>(&self, function: F) -> impl std::future::Future<Output = Result<T, EventLoopClosedError>>
+	where
+		F: FnOnce(&mut ContextHandle<UserEvent>) -> T + Send + 'static,
+		T: Send + 'static,
+	{
+		let (result_tx, result_rx) = oneshot::channel();
+		let posted = self.run_function(move |context| {
+			result_tx.send((function)(context))
+		});
+		async move {
+			posted?;
+			result_rx.await.map_err(|_| EventLoopClosedError)
+		}
+	}
+
+	/// Create a new window, awaiting the result instead of blocking.
+	///
+	/// This is the async counterpart of [`Self::create_window`].
+	pub async fn create_window_async(
+		&self,
+		title: impl Into<String>,
+		options: WindowOptions,
+	) -> Result<WindowProxy<UserEvent>, ProxyCreateWindowError> {
+		let title = title.into();
+		let window_id = self.run_function_async(move |context| {
+			context.create_window(title, options)
+				.map(|window| window.id())
+		}).await??;
+
+		Ok(WindowProxy::new(window_id, self.clone()))
+	}
+
+	/// Destroy a window, awaiting the result instead of blocking.
+	///
+	/// This is the async counterpart of [`Self::destroy_window`].
+	pub async fn destroy_window_async(
+		&self,
+		window_id: WindowId,
+	) -> Result<(), ProxyWindowOperationError> {
+		self.run_function_async(move |context| {
+			context.destroy_window(window_id)
+		}).await??;
+		Ok(())
+	}
+
+	/// Make a window visible or invisible, awaiting the result instead of blocking.
+	///
+	/// This is the async counterpart of [`Self::set_window_visible`].
+	pub async fn set_window_visible_async(
+		&self,
+		window_id: WindowId,
+		visible: bool,
+	) -> Result<(), ProxyWindowOperationError> {
+		self.run_function_async(move |context| {
+			context.set_window_visible(window_id, visible)
+		}).await??;
+		Ok(())
+	}
+
+	/// Set the shown image for a window, awaiting the result instead of blocking.
+	///
+	/// This is the async counterpart of [`Self::set_window_image`].
+	pub async fn set_window_image_async(
+		&self,
+		window_id: WindowId,
+		name: impl Into<String>,
+		image: impl Into<Image<'static>>,
+	) -> Result<(), ProxyWindowOperationError> {
+		let name = name.into();
+		let image = image.into();
+		self.run_function_async(move |context| {
+			context.set_window_image(window_id, &name, &image)
+		}).await??;
+		Ok(())
+	}
+
+	/// Set an overlay for a window, awaiting the result instead of blocking.
+	///
+	/// This is the async counterpart of [`Self::set_window_overlay`].
+	pub async fn set_window_overlay_async(
+		&self,
+		window_id: WindowId,
+		name: impl Into<String>,
+		image: impl Into<Image<'static>>,
+		visible: bool,
+	) -> Result<(), ProxyWindowOperationError> {
+		let name = name.into();
+		let image = image.into();
+		self.run_function_async(move |context| {
+			context.set_window_overlay(window_id, &name, &image, visible)
+		}).await??;
+		Ok(())
+	}
+
+	/// Remove an overlay from a window, awaiting the result instead of blocking.
+	///
+	/// This is the async counterpart of [`Self::clear_window_overlay`].
+	pub async fn clear_window_overlay_async(
+		&self,
+		window_id: WindowId,
+		name: impl Into<String>,
+	) -> Result<(), ProxyWindowOperationError> {
+		let name = name.into();
+		self.run_function_async(move |context| {
+			context.clear_window_overlay(window_id, &name)
+		}).await??;
+		Ok(())
+	}
+
+	/// Make an overlay of a window visible or invisible, awaiting the result instead of blocking.
+	///
+	/// This is the async counterpart of [`Self::set_window_overlay_visible`].
+	pub async fn set_window_overlay_visible_async(
+		&self,
+		window_id: WindowId,
+		name: impl Into<String>,
+		visible: bool,
+	) -> Result<(), ProxyWindowOperationError> {
+		let name = name.into();
+		self.run_function_async(move |context| {
+			context.set_window_overlay_visible(window_id, &name, visible)
+		}).await??;
+		Ok(())
+	}
+
 	/// Send a user event to the context.
 	pub fn send_user_event(&self, event: UserEvent) -> Result<(), EventLoopClosedError> {
 		self.event_loop.send_event(ContextEvent::UserEvent(event)).map_err(|_| EventLoopClosedError)
@@ -233,6 +552,162 @@ impl<UserEvent: 'static> WindowProxy<UserEvent> {
 		self.context_proxy.set_window_image(self.window_id, name, image)
 	}
 
+	/// Get the inner size of the window in physical pixels.
+	///
+	/// This uses [`ContextProxy::run_function_wait`] internally, so it blocks until
+	/// the context thread has read the value.
+	///
+	/// # Panics
+	/// Panics if called from within a context event handler; see [`ContextProxy::run_function_wait`].
+	pub fn inner_size(&self) -> Result<[u32; 2], ProxyWindowOperationError> {
+		let window_id = self.window_id;
+		let size = self.context_proxy.run_function_wait(move |context| {
+			context.window(window_id).map(|window| window.inner_size())
+		})??;
+		Ok(size)
+	}
+
+	/// Get the outer position of the window in physical pixels.
+	///
+	/// This uses [`ContextProxy::run_function_wait`] internally, so it blocks until
+	/// the context thread has read the value.
+	///
+	/// # Panics
+	/// Panics if called from within a context event handler; see [`ContextProxy::run_function_wait`].
+	pub fn outer_position(&self) -> Result<[i32; 2], ProxyWindowOperationError> {
+		let window_id = self.window_id;
+		let position = self.context_proxy.run_function_wait(move |context| {
+			context.window(window_id).map(|window| window.outer_position())
+		})??;
+		Ok(position)
+	}
+
+	/// Get the scale factor of the window.
+	///
+	/// This uses [`ContextProxy::run_function_wait`] internally, so it blocks until
+	/// the context thread has read the value.
+	///
+	/// # Panics
+	/// Panics if called from within a context event handler; see [`ContextProxy::run_function_wait`].
+	pub fn scale_factor(&self) -> Result<f64, ProxyWindowOperationError> {
+		let window_id = self.window_id;
+		let scale_factor = self.context_proxy.run_function_wait(move |context| {
+			context.window(window_id).map(|window| window.scale_factor())
+		})??;
+		Ok(scale_factor)
+	}
+
+	/// Check if the window is currently visible.
+	///
+	/// This uses [`ContextProxy::run_function_wait`] internally, so it blocks until
+	/// the context thread has read the value.
+	///
+	/// # Panics
+	/// Panics if called from within a context event handler; see [`ContextProxy::run_function_wait`].
+	pub fn is_visible(&self) -> Result<bool, ProxyWindowOperationError> {
+		let window_id = self.window_id;
+		let visible = self.context_proxy.run_function_wait(move |context| {
+			context.window(window_id).map(|window| window.is_visible())
+		})??;
+		Ok(visible)
+	}
+
+	/// Get the image info of the image currently displayed in the window.
+	///
+	/// Returns [`None`] if no image has been set for the window yet.
+	///
+	/// This uses [`ContextProxy::run_function_wait`] internally, so it blocks until
+	/// the context thread has read the value.
+	///
+	/// # Panics
+	/// Panics if called from within a context event handler; see [`ContextProxy::run_function_wait`].
+	pub fn image_info(&self) -> Result<Option<ImageInfo>, ProxyWindowOperationError> {
+		let window_id = self.window_id;
+		let info = self.context_proxy.run_function_wait(move |context| {
+			context.window(window_id).map(|window| window.image_info().cloned())
+		})??;
+		Ok(info)
+	}
+
+	/// Destroy the window, awaiting the result instead of blocking.
+	pub async fn destroy_async(&self) -> Result<(), ProxyWindowOperationError> {
+		self.context_proxy.destroy_window_async(self.window_id).await
+	}
+
+	/// Make the window visible or invisible, awaiting the result instead of blocking.
+	pub async fn set_visible_async(
+		&self,
+		visible: bool,
+	) -> Result<(), ProxyWindowOperationError> {
+		self.context_proxy.set_window_visible_async(self.window_id, visible).await
+	}
+
+	/// Set the image of the window, awaiting the result instead of blocking.
+	pub async fn set_image_async(
+		&self,
+		name: impl Into<String>,
+		image: Image<'static>,
+	) -> Result<(), ProxyWindowOperationError> {
+		self.context_proxy.set_window_image_async(self.window_id, name, image).await
+	}
+
+	/// Set a named overlay for the window, awaiting the result instead of blocking.
+	pub async fn set_overlay_async(
+		&self,
+		name: impl Into<String>,
+		image: impl Into<Image<'static>>,
+		visible: bool,
+	) -> Result<(), ProxyWindowOperationError> {
+		self.context_proxy.set_window_overlay_async(self.window_id, name, image, visible).await
+	}
+
+	/// Remove a named overlay from the window, awaiting the result instead of blocking.
+	pub async fn clear_overlay_async(
+		&self,
+		name: impl Into<String>,
+	) -> Result<(), ProxyWindowOperationError> {
+		self.context_proxy.clear_window_overlay_async(self.window_id, name).await
+	}
+
+	/// Make a named overlay of the window visible or invisible, awaiting the result instead of blocking.
+	pub async fn set_overlay_visible_async(
+		&self,
+		name: impl Into<String>,
+		visible: bool,
+	) -> Result<(), ProxyWindowOperationError> {
+		self.context_proxy.set_window_overlay_visible_async(self.window_id, name, visible).await
+	}
+
+	/// Set a named overlay for the window.
+	///
+	/// Overlays are drawn on top of the base image set with [`Self::set_image`].
+	/// Setting an overlay with an existing name replaces it.
+	pub fn set_overlay(
+		&self,
+		name: impl Into<String>,
+		image: impl Into<Image<'static>>,
+		visible: bool,
+	) -> Result<(), ProxyWindowOperationError> {
+		self.context_proxy.set_window_overlay(self.window_id, name, image, visible)
+	}
+
+	/// Remove a named overlay from the window.
+	pub fn clear_overlay(
+		&self,
+		name: impl Into<String>,
+	) -> Result<(), ProxyWindowOperationError> {
+		self.context_proxy.clear_window_overlay(self.window_id, name)
+	}
+
+	/// Make a named overlay of the window visible or invisible.
+	pub fn set_overlay_visible(
+		&self,
+		name: impl Into<String>,
+		visible: bool,
+	) -> Result<(), ProxyWindowOperationError> {
+		self.context_proxy.set_window_overlay_visible(self.window_id, name, visible)
+	}
+
 	/// Add an event handler for a specific window.
 	///
 	/// Events that are already queued with the event loop will not be passed to the handler.
@@ -241,8 +716,23 @@ impl<UserEvent: 'static> WindowProxy<UserEvent> {
 	/// To avoid blocking, you can use [`ContextHandle::run_function`] to post a lambda that adds an error handler instead.
 	pub fn add_window_event_handler<F>(&mut self, handler: F) -> Result<(), ProxyWindowOperationError>
 	where
-		F: FnMut(WindowHandle<UserEvent>, &mut WindowEvent) -> EventHandlerOutput + Send + 'static,
+		F: FnMut(WindowHandle<UserEvent>, &mut WindowEvent, &mut EventHandlerControlFlow) + Send + 'static,
 	{
 		self.context_proxy.add_window_event_handler(self.window_id, handler)
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn context_thread_guard_toggles_the_marker() {
+		assert!(!ON_CONTEXT_THREAD.with(std::cell::Cell::get));
+		{
+			let _guard = ContextThreadGuard::new();
+			assert!(ON_CONTEXT_THREAD.with(std::cell::Cell::get));
+		}
+		assert!(!ON_CONTEXT_THREAD.with(std::cell::Cell::get));
+	}
+}
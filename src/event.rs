@@ -0,0 +1,47 @@
+//! Event types and the control object passed to event handlers.
+
+/// An event delivered to the global event handlers registered on the context.
+pub enum Event<UserEvent: 'static> {
+	/// An event for a specific window.
+	WindowEvent(WindowEvent),
+
+	/// A custom user event sent through [`ContextProxy::send_user_event`][crate::ContextProxy::send_user_event].
+	UserEvent(UserEvent),
+
+	/// The event loop was woken through a [`ContextWaker`][crate::ContextWaker].
+	///
+	/// This event carries no payload; it exists so handlers can drain an external queue
+	/// that the waker's owner fills. See [`ContextWaker`][crate::ContextWaker] for details.
+	Wake,
+}
+
+/// Control object passed to event handlers to influence event dispatch.
+///
+/// A handler receives this by `&mut` reference and may set flags on it to change
+/// how the context thread continues dispatching the current event:
+///
+/// * [`stop_propagation`][Self::stop_propagation] prevents any handlers that would run
+///   after this one from seeing the event. Window handlers run before the global handlers,
+///   so a window handler that stops propagation also hides the event from every global handler.
+/// * [`remove_handler`][Self::remove_handler] unregisters the handler after it returns,
+///   which is useful for one-shot handlers such as "wait for the next click".
+#[derive(Debug, Clone, Default)]
+pub struct EventHandlerControlFlow {
+	/// Whether to stop passing the event to later handlers.
+	pub(crate) stop_propagation: bool,
+
+	/// Whether to remove the handler after it returns.
+	pub(crate) remove_handler: bool,
+}
+
+impl EventHandlerControlFlow {
+	/// Stop the event from propagating to handlers that would run after this one.
+	pub fn stop_propagation(&mut self) {
+		self.stop_propagation = true;
+	}
+
+	/// Remove this handler from the registry after it returns.
+	pub fn remove_handler(&mut self) {
+		self.remove_handler = true;
+	}
+}
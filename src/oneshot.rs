@@ -0,0 +1,168 @@
+//! A minimal single-value channel.
+//!
+//! This is used internally to get return values back from functions posted to the
+//! context thread. A [`Sender`] can transmit exactly one value, which a [`Receiver`]
+//! can either block on with [`Receiver::recv`] or await as a [`Future`].
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::Condvar;
+use std::task::Context;
+use std::task::Poll;
+use std::task::Waker;
+
+/// Error returned when the [`Sender`] was dropped without sending a value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecvError;
+
+impl std::fmt::Display for RecvError {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(f, "the sender was dropped without sending a value")
+	}
+}
+
+impl std::error::Error for RecvError {}
+
+/// The shared state between a [`Sender`] and a [`Receiver`].
+struct Inner<T> {
+	/// The transmitted value, if any.
+	value: Option<T>,
+
+	/// Set when the sender is dropped so the receiver can stop waiting.
+	sender_dropped: bool,
+
+	/// The task waker registered by an awaiting [`Receiver`], if any.
+	waker: Option<Waker>,
+}
+
+/// The sending half of a oneshot channel.
+pub struct Sender<T> {
+	inner: Arc<(Mutex<Inner<T>>, Condvar)>,
+}
+
+/// The receiving half of a oneshot channel.
+pub struct Receiver<T> {
+	inner: Arc<(Mutex<Inner<T>>, Condvar)>,
+}
+
+/// Create a new oneshot channel.
+pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+	let inner = Arc::new((
+		Mutex::new(Inner { value: None, sender_dropped: false, waker: None }),
+		Condvar::new(),
+	));
+	(Sender { inner: inner.clone() }, Receiver { inner })
+}
+
+impl<T> Sender<T> {
+	/// Send the value to the receiver.
+	///
+	/// This wakes both a blocking [`Receiver::recv`] and an awaiting [`Receiver`] future.
+	pub fn send(self, value: T) {
+		let (mutex, condvar) = &*self.inner;
+		let waker = {
+			let mut inner = mutex.lock().unwrap();
+			inner.value = Some(value);
+			inner.waker.take()
+		};
+		condvar.notify_all();
+		if let Some(waker) = waker {
+			waker.wake();
+		}
+	}
+}
+
+impl<T> Drop for Sender<T> {
+	fn drop(&mut self) {
+		let (mutex, condvar) = &*self.inner;
+		let waker = {
+			let mut inner = mutex.lock().unwrap();
+			if inner.value.is_some() {
+				return;
+			}
+			inner.sender_dropped = true;
+			inner.waker.take()
+		};
+		condvar.notify_all();
+		if let Some(waker) = waker {
+			waker.wake();
+		}
+	}
+}
+
+impl<T> Receiver<T> {
+	/// Block the current thread until a value is received.
+	///
+	/// Returns [`RecvError`] if the sender was dropped without sending a value.
+	pub fn recv(self) -> Result<T, RecvError> {
+		let (mutex, condvar) = &*self.inner;
+		let mut inner = mutex.lock().unwrap();
+		loop {
+			if let Some(value) = inner.value.take() {
+				return Ok(value);
+			}
+			if inner.sender_dropped {
+				return Err(RecvError);
+			}
+			inner = condvar.wait(inner).unwrap();
+		}
+	}
+}
+
+impl<T> Future for Receiver<T> {
+	type Output = Result<T, RecvError>;
+
+	fn poll(self: Pin<&mut Self>, context: &mut Context) -> Poll<Self::Output> {
+		let (mutex, _condvar) = &*self.inner;
+		let mut inner = mutex.lock().unwrap();
+		if let Some(value) = inner.value.take() {
+			Poll::Ready(Ok(value))
+		} else if inner.sender_dropped {
+			Poll::Ready(Err(RecvError))
+		} else {
+			// Register (or refresh) our waker so `send`/`drop` can wake the task.
+			inner.waker = Some(context.waker().clone());
+			Poll::Pending
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use std::task::Wake;
+
+	/// A no-op waker so we can poll a future by hand in the tests.
+	struct NoopWaker;
+
+	impl Wake for NoopWaker {
+		fn wake(self: Arc<Self>) {}
+	}
+
+	#[test]
+	fn recv_returns_the_sent_value() {
+		let (tx, rx) = channel();
+		tx.send(42);
+		assert_eq!(rx.recv(), Ok(42));
+	}
+
+	#[test]
+	fn recv_errors_when_sender_dropped() {
+		let (tx, rx) = channel::<u32>();
+		drop(tx);
+		assert_eq!(rx.recv(), Err(RecvError));
+	}
+
+	#[test]
+	fn future_is_pending_then_ready() {
+		let (tx, mut rx) = channel();
+		let waker = Arc::new(NoopWaker).into();
+		let mut context = Context::from_waker(&waker);
+
+		assert_eq!(Pin::new(&mut rx).poll(&mut context), Poll::Pending);
+		tx.send(10);
+		assert_eq!(Pin::new(&mut rx).poll(&mut context), Poll::Ready(Ok(10)));
+	}
+}